@@ -1,5 +1,15 @@
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
 use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use tokio::sync::RwLock;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
 
 /// Enhanced DLMM Client for Rust
 /// 
@@ -7,6 +17,13 @@ use anyhow::Result;
 /// Features include: quotes, swaps, liquidity management, and advanced strategies.
 pub struct EnhancedSarosDLMM {
     config: DlmmConfig,
+    /// `None` for a pair with no concentrated-liquidity bin state, in which
+    /// case `reserves` must be set and quoting falls back to constant-product.
+    pool_state: Option<DlmmPoolState>,
+    rate_service: Box<dyn RateService>,
+    /// Constant-product reserves used when no concentrated-liquidity state
+    /// is available for the pair (see `PoolReserves`).
+    reserves: Option<PoolReserves>,
 }
 
 /// DLMM configuration
@@ -14,6 +31,158 @@ pub struct EnhancedSarosDLMM {
 pub struct DlmmConfig {
     pub network: String,
     pub slippage: f64,
+    /// Base leg of the pair this client quotes, e.g. `"SOL"`.
+    pub base_symbol: String,
+    /// Quote leg of the pair this client quotes, e.g. `"USDC"`.
+    pub quote_symbol: String,
+}
+
+/// Supplies the live base/quote mid-price a quote is priced against.
+///
+/// Implementations range from a fixed rate for tests to a streaming
+/// WebSocket feed for production use, so `EnhancedSarosDLMM` can be driven
+/// by either without changing its quoting logic.
+#[async_trait]
+pub trait RateService: Send + Sync {
+    async fn current_rate(&self, base: &str, quote: &str) -> Result<f64>;
+}
+
+/// A constant rate, useful for unit tests and offline quoting.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedRate {
+    pub rate: f64,
+}
+
+#[async_trait]
+impl RateService for FixedRate {
+    async fn current_rate(&self, _base: &str, _quote: &str) -> Result<f64> {
+        Ok(self.rate)
+    }
+}
+
+/// Subscribes to an external exchange ticker over a WebSocket and caches the
+/// latest mid-price per pair, reconnecting with backoff if the connection
+/// drops. Mirrors the dynamic-rate pattern market-maker bots use to keep a
+/// hot, lock-guarded view of the book without blocking quote callers on I/O.
+pub struct StreamingRate {
+    cache: Arc<RwLock<HashMap<(String, String), f64>>>,
+}
+
+impl StreamingRate {
+    /// Connect to `ws_url` and start caching mid-prices in the background.
+    pub fn connect(ws_url: impl Into<String>) -> Self {
+        let cache = Arc::new(RwLock::new(HashMap::new()));
+        let ws_url = ws_url.into();
+
+        tokio::spawn(Self::run(ws_url, Arc::clone(&cache)));
+
+        Self { cache }
+    }
+
+    async fn run(ws_url: String, cache: Arc<RwLock<HashMap<(String, String), f64>>>) {
+        let mut backoff = Duration::from_millis(500);
+        const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+        loop {
+            match connect_async(&ws_url).await {
+                Ok((stream, _)) => {
+                    backoff = Duration::from_millis(500);
+                    let (_write, mut read) = stream.split();
+
+                    while let Some(msg) = read.next().await {
+                        let Ok(Message::Text(text)) = msg else {
+                            continue;
+                        };
+                        if let Some((pair, mid)) = parse_ticker(&text) {
+                            cache.write().await.insert(pair, mid);
+                        }
+                    }
+                    // Stream ended; fall through and reconnect with backoff.
+                }
+                Err(_) => {
+                    // Connection attempt failed; fall through to backoff below.
+                }
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
+#[async_trait]
+impl RateService for StreamingRate {
+    async fn current_rate(&self, base: &str, quote: &str) -> Result<f64> {
+        let key = (base.to_string(), quote.to_string());
+        self.cache
+            .read()
+            .await
+            .get(&key)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("no cached rate yet for {base}/{quote}"))
+    }
+}
+
+/// Relative nudge used to bias a computed value away from over-quoting,
+/// since `f64` arithmetic alone doesn't round in a particular direction.
+const ROUNDING_EPSILON: f64 = 1e-12;
+
+fn round_up(value: f64) -> f64 {
+    value * (1.0 + ROUNDING_EPSILON)
+}
+
+fn round_down(value: f64) -> f64 {
+    value * (1.0 - ROUNDING_EPSILON)
+}
+
+/// Parses a `{"pair": "SOL/USDC", "bid": ..., "ask": ...}`-shaped ticker
+/// message into a `((base, quote), mid_price)` tuple.
+fn parse_ticker(text: &str) -> Option<((String, String), f64)> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let pair = value.get("pair")?.as_str()?;
+    let (base, quote) = pair.split_once('/')?;
+    let bid = value.get("bid")?.as_f64()?;
+    let ask = value.get("ask")?.as_f64()?;
+    Some(((base.to_string(), quote.to_string()), (bid + ask) / 2.0))
+}
+
+/// Concrete concentrated-liquidity pool state a quote is computed against,
+/// mirroring the active bin of a DLMM pair.
+#[derive(Clone, Copy, Debug)]
+pub struct DlmmPoolState {
+    /// Active liquidity `L` available at the current bin.
+    pub liquidity: f64,
+    /// Current sqrt price, as token1 (quote) per token0 (base).
+    pub sqrt_price: f64,
+    /// Bin step in basis points, as configured on the pair.
+    pub bin_step: u16,
+    /// Swap fee rate, e.g. `0.003` for 0.3%.
+    pub fee_rate: f64,
+}
+
+/// A plain constant-product (`x * y = k`) pool, used as the quoting fallback
+/// for pairs that don't have concentrated-liquidity bin state.
+#[derive(Clone, Copy, Debug)]
+pub struct PoolReserves {
+    /// Reserve of the base token (token0), the side sold into the pool on a
+    /// base-to-quote swap; quote-to-base swaps use it as the output reserve.
+    pub reserve_in: f64,
+    /// Reserve of the quote token (token1), the side bought out of the pool
+    /// on a base-to-quote swap; quote-to-base swaps use it as the input reserve.
+    pub reserve_out: f64,
+    /// Swap fee rate, e.g. `0.003` for 0.3%.
+    pub fee_rate: f64,
+}
+
+/// Derived, point-in-time statistics for a pair.
+#[derive(Clone, Copy, Debug)]
+pub struct PoolStats {
+    /// Total value locked, denominated in the quote token.
+    pub tvl: f64,
+    /// Current spot price (quote per base).
+    pub spot_price: f64,
+    /// Estimated fees accrued over the last 24h.
+    pub fees_24h: f64,
 }
 
 /// Token information
@@ -31,6 +200,9 @@ pub struct SwapParams {
     pub output_token: String,
     pub amount: f64,
     pub wallet_public_key: String,
+    /// Per-call slippage tolerance in percent, overriding `DlmmConfig::slippage`
+    /// for this swap only.
+    pub slippage_override: Option<f64>,
 }
 
 /// Swap result
@@ -48,26 +220,238 @@ pub struct QuoteResult {
     pub expected_output: f64,
     pub price_impact: f64,
     pub fee: f64,
+    /// Minimum output accepted before the swap aborts as slippage-exceeded.
+    pub min_received: f64,
+}
+
+/// Errors specific to DLMM quoting and swap execution.
+#[derive(Debug)]
+pub enum DlmmError {
+    /// `DlmmConfig::slippage` (or a per-call override) was outside `(0.0, 100.0]`.
+    InvalidSlippage(f64),
+    /// A fresh quote taken just before submission fell below the slippage floor.
+    SlippageExceeded {
+        expected: f64,
+        actual: f64,
+        min_received: f64,
+    },
+}
+
+impl fmt::Display for DlmmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DlmmError::InvalidSlippage(slippage) => {
+                write!(f, "slippage must be in (0.0, 100.0], got {slippage}")
+            }
+            DlmmError::SlippageExceeded {
+                expected,
+                actual,
+                min_received,
+            } => write!(
+                f,
+                "slippage exceeded: expected {expected}, actual {actual}, min received {min_received}"
+            ),
+        }
+    }
+}
+
+impl Error for DlmmError {}
+
+/// How liquidity is weighted across the bins in a deposit range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LiquidityStrategy {
+    /// Uniform liquidity across every bin in the range.
+    Spot,
+    /// Liquidity concentrated around the pool's current active bin.
+    Curve,
+    /// Liquidity weighted toward the edges of the range.
+    BidAsk,
+}
+
+/// A token symbol and amount, e.g. a leg of a liquidity deposit.
+#[derive(Clone, Debug)]
+pub struct TokenAmount {
+    pub symbol: String,
+    pub amount: f64,
+}
+
+/// The token0/token1 split actually placed into one bin.
+#[derive(Clone, Debug)]
+pub struct BinAllocation {
+    pub bin_id: i32,
+    pub amount0: f64,
+    pub amount1: f64,
+}
+
+/// The result of an `add_liquidity` call: what was placed, and what was left over.
+#[derive(Clone, Debug)]
+pub struct LiquidityPosition {
+    pub lower_bin: i32,
+    pub upper_bin: i32,
+    pub strategy: LiquidityStrategy,
+    pub allocations: Vec<BinAllocation>,
+    pub leftover: Vec<TokenAmount>,
 }
 
 impl EnhancedSarosDLMM {
-    /// Create a new enhanced DLMM client
+    /// Create a new enhanced DLMM client backed by a fixed, offline rate.
     pub fn new(config: DlmmConfig) -> Result<Self, Box<dyn Error>> {
-        Ok(Self { config })
+        Self::with_rate_service(config, Box::new(FixedRate { rate: 100.0 }))
+    }
+
+    /// Create a new enhanced DLMM client driven by the given `RateService`,
+    /// e.g. a `StreamingRate` for live market quotes in production.
+    pub fn with_rate_service(
+        config: DlmmConfig,
+        rate_service: Box<dyn RateService>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if !(config.slippage > 0.0 && config.slippage <= 100.0) {
+            return Err(Box::new(DlmmError::InvalidSlippage(config.slippage)));
+        }
+
+        Ok(Self {
+            config,
+            pool_state: Some(DlmmPoolState {
+                liquidity: 1_000_000.0,
+                sqrt_price: 10.0, // sqrt(100), overwritten per-quote by `rate_service`
+                bin_step: 10,
+                fee_rate: 0.003,
+            }),
+            rate_service,
+            reserves: None,
+        })
+    }
+
+    /// Attach constant-product reserves, used as the quoting fallback for
+    /// pairs with no concentrated-liquidity bin state. Clears `pool_state`,
+    /// since a pair is either a DLMM bin market or a constant-product one,
+    /// never both.
+    pub fn with_reserves(mut self, reserves: PoolReserves) -> Self {
+        self.reserves = Some(reserves);
+        self.pool_state = None;
+        self
+    }
+
+    /// The live base/quote mid-price, shared by every method that needs a
+    /// price but not necessarily bin state.
+    async fn current_rate(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self
+            .rate_service
+            .current_rate(&self.config.base_symbol, &self.config.quote_symbol)
+            .await?)
+    }
+
+    /// A snapshot of `pool_state` with `sqrt_price` refreshed from the live
+    /// `rate_service`, the single source of truth quotes and stats share.
+    /// `None` if this pair has no concentrated-liquidity bin state.
+    async fn current_pool(&self) -> Result<Option<DlmmPoolState>, Box<dyn Error>> {
+        let Some(pool_state) = self.pool_state else {
+            return Ok(None);
+        };
+        let rate = self.current_rate().await?;
+        Ok(Some(DlmmPoolState {
+            sqrt_price: rate.sqrt(),
+            ..pool_state
+        }))
+    }
+
+    /// Current spot price (quote per base), read from the same `rate_service`
+    /// that `get_quote` and `get_pool_stats` price against.
+    pub async fn spot_price(&self) -> Result<f64, Box<dyn Error>> {
+        self.current_rate().await
     }
 
     /// Get comprehensive quote for a DLMM swap
+    ///
+    /// Walks the active bin's constant-liquidity curve from the pool's current
+    /// `sqrt_price`, the same step math the DLMM program uses on-chain. Falls
+    /// back to constant-product pricing against `reserves` when the pair has
+    /// no concentrated-liquidity state.
     pub async fn get_quote(&self, params: &SwapParams) -> Result<QuoteResult, Box<dyn Error>> {
-        // Simulate quote calculation
-        let expected_output = params.amount * 100.0; // Assume 1 SOL = 100 USDC
-        let price_impact = (params.amount / 1_000_000.0) * 0.05; // 0.05% per $1M
-        let fee = params.amount * 0.003; // 0.3% fee
+        let pool = self.current_pool().await?;
+        let fee_rate = pool
+            .as_ref()
+            .map(|p| p.fee_rate)
+            .or(self.reserves.as_ref().map(|r| r.fee_rate))
+            .ok_or("no concentrated-liquidity state and no fallback PoolReserves configured")?;
+        let fee = params.amount * fee_rate;
+        let amount_in_after_fee = params.amount * (1.0 - fee_rate);
+
+        // Derived from the `pool`/`rate_service` read already done above rather
+        // than a second `current_rate()` call, so a time-varying rate source
+        // can't shift mid-quote (e.g. between a quote and its re-quote).
+        let spot_price = match &pool {
+            Some(pool) => pool.sqrt_price * pool.sqrt_price,
+            None => self.current_rate().await?,
+        };
+
+        // Selling token0 (base) for token1 (quote) moves sqrt_price down;
+        // selling token1 for token0 moves it up. Route off the client's own
+        // configured pair rather than a hardcoded symbol, and reject swaps
+        // for any other pair instead of silently misrouting them.
+        let selling_token0 = if params.input_token == self.config.base_symbol
+            && params.output_token == self.config.quote_symbol
+        {
+            true
+        } else if params.input_token == self.config.quote_symbol
+            && params.output_token == self.config.base_symbol
+        {
+            false
+        } else {
+            return Err(format!(
+                "swap pair {}/{} does not match configured pair {}/{}",
+                params.input_token,
+                params.output_token,
+                self.config.base_symbol,
+                self.config.quote_symbol
+            )
+            .into());
+        };
+
+        let (expected_output, execution_price) = if let Some(pool) = &pool {
+            if selling_token0 {
+                let sqrt_next = (pool.liquidity * pool.sqrt_price)
+                    / (pool.liquidity + amount_in_after_fee * pool.sqrt_price);
+                // Round the consumed sqrt price step up so a smaller price move
+                // is assumed, which yields less token1 out and never over-quotes.
+                let sqrt_next = round_up(sqrt_next);
+                let amount1_out = pool.liquidity * (pool.sqrt_price - sqrt_next);
+                (amount1_out, amount1_out / params.amount)
+            } else {
+                let sqrt_next = pool.sqrt_price + amount_in_after_fee / pool.liquidity;
+                let amount0_out =
+                    pool.liquidity * (1.0 / sqrt_next - 1.0 / pool.sqrt_price).abs();
+                // Round the token0 output down for token1 input to avoid over-quoting.
+                let amount0_out = round_down(amount0_out);
+                (amount0_out, amount0_out / params.amount)
+            }
+        } else {
+            let reserves = self.reserves.as_ref().ok_or(
+                "no concentrated-liquidity state and no fallback PoolReserves configured",
+            )?;
+            let (reserve_in, reserve_out) = if selling_token0 {
+                (reserves.reserve_in, reserves.reserve_out)
+            } else {
+                (reserves.reserve_out, reserves.reserve_in)
+            };
+            let out = (reserve_out * amount_in_after_fee) / (reserve_in + amount_in_after_fee);
+            (out, out / params.amount)
+        };
+
+        let price_impact = (spot_price - execution_price) / spot_price;
+
+        let slippage = params.slippage_override.unwrap_or(self.config.slippage);
+        if !(slippage > 0.0 && slippage <= 100.0) {
+            return Err(Box::new(DlmmError::InvalidSlippage(slippage)));
+        }
+        let min_received = expected_output * (1.0 - slippage / 100.0);
 
         Ok(QuoteResult {
             input_amount: params.amount,
             expected_output,
             price_impact,
             fee,
+            min_received,
         })
     }
 
@@ -75,9 +459,20 @@ impl EnhancedSarosDLMM {
     pub async fn execute_swap(&self, params: &SwapParams) -> Result<SwapResult, Box<dyn Error>> {
         // Validate inputs
         self.validate_swap_params(params)?;
-        
+
         // Get quote first
-        let _quote = self.get_quote(params).await?;
+        let quote = self.get_quote(params).await?;
+
+        // Re-quote right before submission so a stale quote can't slip the
+        // fill past what the caller agreed to.
+        let fresh_quote = self.get_quote(params).await?;
+        if fresh_quote.expected_output < quote.min_received {
+            return Err(Box::new(DlmmError::SlippageExceeded {
+                expected: quote.expected_output,
+                actual: fresh_quote.expected_output,
+                min_received: quote.min_received,
+            }));
+        }
 
         // Simulate successful swap
         Ok(SwapResult {
@@ -87,10 +482,206 @@ impl EnhancedSarosDLMM {
         })
     }
 
-    /// Get pool statistics
-    pub async fn get_pool_stats(&self, _pair_address: &str) -> Result<f64, Box<dyn Error>> {
-        // Simulate pool stats
-        Ok(2_000_000.0) // $2M total liquidity
+    /// Get pool statistics, derived from the same reserves/bin state `get_quote` prices against.
+    pub async fn get_pool_stats(&self, _pair_address: &str) -> Result<PoolStats, Box<dyn Error>> {
+        let pool = self.current_pool().await?;
+        // Derived from `pool`'s already-fetched rate where possible, rather
+        // than a second `current_rate()` call against a time-varying source.
+        let spot_price = match &pool {
+            Some(pool) => pool.sqrt_price * pool.sqrt_price,
+            None => self.current_rate().await?,
+        };
+
+        let (tvl, fee_rate) = match (&pool, &self.reserves) {
+            (Some(pool), _) => {
+                // Value of a constant-liquidity position at the current price, `2 * L * sqrt(P)`.
+                (2.0 * pool.liquidity * pool.sqrt_price, pool.fee_rate)
+            }
+            (None, Some(reserves)) => (
+                reserves.reserve_in * spot_price + reserves.reserve_out,
+                reserves.fee_rate,
+            ),
+            (None, None) => {
+                return Err(
+                    "no concentrated-liquidity state and no fallback PoolReserves configured"
+                        .into(),
+                )
+            }
+        };
+
+        // No on-chain volume feed in this example, so fee accrual is
+        // approximated as one full turnover of the pool's TVL per day.
+        let fees_24h = tvl * fee_rate;
+
+        Ok(PoolStats {
+            tvl,
+            spot_price,
+            fees_24h,
+        })
+    }
+
+    /// Deposit liquidity across `[lower_bin, upper_bin]` using the given
+    /// distribution strategy, from an explicit list of provided tokens.
+    ///
+    /// Each bin's required token0/token1 split is derived from that bin's
+    /// price bounds, so deposits can be single-sided (only one token in
+    /// `tokens_provided`) or double-sided. Whichever token runs out first
+    /// caps the liquidity placed; anything left over is returned rather
+    /// than silently dropped. Prices off the live `rate_service`, the same
+    /// as `get_quote`, so `Curve`/`BidAsk` concentrate around the pair's
+    /// actual current price rather than a stale construction-time value.
+    pub async fn add_liquidity(
+        &self,
+        lower_bin: i32,
+        upper_bin: i32,
+        strategy: LiquidityStrategy,
+        tokens_provided: &[TokenAmount],
+    ) -> Result<LiquidityPosition, Box<dyn Error>> {
+        if lower_bin > upper_bin {
+            return Err("lower_bin must not be greater than upper_bin".into());
+        }
+
+        let pool = self
+            .current_pool()
+            .await?
+            .ok_or("this pair has no concentrated-liquidity bin state to deposit into")?;
+
+        let token0_amount = tokens_provided
+            .iter()
+            .find(|t| t.symbol == self.config.base_symbol)
+            .map(|t| t.amount)
+            .unwrap_or(0.0);
+        let token1_amount = tokens_provided
+            .iter()
+            .find(|t| t.symbol == self.config.quote_symbol)
+            .map(|t| t.amount)
+            .unwrap_or(0.0);
+
+        let bin_ids: Vec<i32> = (lower_bin..=upper_bin).collect();
+        let weights = Self::strategy_weights(&pool, &bin_ids, strategy);
+
+        // Per-unit-liquidity token0/token1 coefficients, summed across bins.
+        let mut coef0 = 0.0;
+        let mut coef1 = 0.0;
+        let mut bounds = Vec::with_capacity(bin_ids.len());
+        for (&bin_id, &weight) in bin_ids.iter().zip(&weights) {
+            let sqrt_lower = Self::bin_sqrt_price(&pool, bin_id);
+            let sqrt_upper = Self::bin_sqrt_price(&pool, bin_id + 1);
+            coef0 += weight * (1.0 / sqrt_lower - 1.0 / sqrt_upper);
+            coef1 += weight * (sqrt_upper - sqrt_lower);
+            bounds.push((bin_id, weight, sqrt_lower, sqrt_upper));
+        }
+
+        // Only constrain on a side the caller actually provided — a zero
+        // amount here means "single-sided deposit", not "cap liquidity at 0".
+        let l0 = (coef0 > 0.0 && token0_amount > 0.0).then(|| token0_amount / coef0);
+        let l1 = (coef1 > 0.0 && token1_amount > 0.0).then(|| token1_amount / coef1);
+        let liquidity = match (l0, l1) {
+            (Some(l0), Some(l1)) => l0.min(l1),
+            (Some(l0), None) => l0,
+            (None, Some(l1)) => l1,
+            (None, None) => 0.0,
+        };
+
+        let mut allocations = Vec::with_capacity(bounds.len());
+        let mut placed0 = 0.0;
+        let mut placed1 = 0.0;
+        for (bin_id, weight, sqrt_lower, sqrt_upper) in bounds {
+            let bin_liquidity = liquidity * weight;
+            // A side the caller didn't provide stays empty rather than being
+            // allocated from tokens that were never supplied.
+            let amount0 = if l0.is_some() {
+                bin_liquidity * (1.0 / sqrt_lower - 1.0 / sqrt_upper)
+            } else {
+                0.0
+            };
+            let amount1 = if l1.is_some() {
+                bin_liquidity * (sqrt_upper - sqrt_lower)
+            } else {
+                0.0
+            };
+            placed0 += amount0;
+            placed1 += amount1;
+            allocations.push(BinAllocation {
+                bin_id,
+                amount0,
+                amount1,
+            });
+        }
+
+        let leftover = vec![
+            TokenAmount {
+                symbol: self.config.base_symbol.clone(),
+                amount: (token0_amount - placed0).max(0.0),
+            },
+            TokenAmount {
+                symbol: self.config.quote_symbol.clone(),
+                amount: (token1_amount - placed1).max(0.0),
+            },
+        ];
+
+        Ok(LiquidityPosition {
+            lower_bin,
+            upper_bin,
+            strategy,
+            allocations,
+            leftover,
+        })
+    }
+
+    /// Withdraw a position, returning the reclaimed `(token0, token1)` amounts.
+    pub fn remove_liquidity(
+        &self,
+        position: &LiquidityPosition,
+    ) -> Result<(f64, f64), Box<dyn Error>> {
+        let amount0 = position.allocations.iter().map(|a| a.amount0).sum();
+        let amount1 = position.allocations.iter().map(|a| a.amount1).sum();
+        Ok((amount0, amount1))
+    }
+
+    /// Price of bin `bin_id` in terms of token1 per token0, per the standard
+    /// DLMM bin formula `(1 + bin_step / 10_000) ^ bin_id`.
+    fn bin_price(pool: &DlmmPoolState, bin_id: i32) -> f64 {
+        (1.0 + pool.bin_step as f64 / 10_000.0).powi(bin_id)
+    }
+
+    fn bin_sqrt_price(pool: &DlmmPoolState, bin_id: i32) -> f64 {
+        Self::bin_price(pool, bin_id).sqrt()
+    }
+
+    /// The bin closest to the pool's current spot price.
+    fn active_bin(pool: &DlmmPoolState) -> i32 {
+        let spot_price = pool.sqrt_price * pool.sqrt_price;
+        let base = 1.0 + pool.bin_step as f64 / 10_000.0;
+        (spot_price.ln() / base.ln()).round() as i32
+    }
+
+    /// Per-bin weights for a strategy, normalized to sum to `1.0`.
+    fn strategy_weights(
+        pool: &DlmmPoolState,
+        bin_ids: &[i32],
+        strategy: LiquidityStrategy,
+    ) -> Vec<f64> {
+        let raw: Vec<f64> = match strategy {
+            LiquidityStrategy::Spot => bin_ids.iter().map(|_| 1.0).collect(),
+            LiquidityStrategy::Curve => {
+                let active = Self::active_bin(pool);
+                bin_ids
+                    .iter()
+                    .map(|&bin_id| 1.0 / (1.0 + (bin_id - active).abs() as f64))
+                    .collect()
+            }
+            LiquidityStrategy::BidAsk => {
+                let center = (bin_ids[0] + bin_ids[bin_ids.len() - 1]) as f64 / 2.0;
+                bin_ids
+                    .iter()
+                    .map(|&bin_id| 1.0 + (bin_id as f64 - center).abs())
+                    .collect()
+            }
+        };
+
+        let total: f64 = raw.iter().sum();
+        raw.iter().map(|w| w / total).collect()
     }
 
     // Helper methods
@@ -117,10 +708,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let config = DlmmConfig {
         network: "mainnet-beta".to_string(),
         slippage: 0.5,
+        base_symbol: "SOL".to_string(),
+        quote_symbol: "USDC".to_string(),
     };
     
+    let network = config.network.clone();
     let dlmm = EnhancedSarosDLMM::new(config)?;
-    println!("📡 Connected to {}\n", config.network);
+    println!("📡 Connected to {}\n", network);
 
     // Example wallet
     let wallet_public_key = "REPLACE_WITH_YOUR_PUBLIC_KEY";
@@ -132,6 +726,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         output_token: "USDC".to_string(),
         amount: 1.0,
         wallet_public_key: wallet_public_key.to_string(),
+        slippage_override: None,
     };
     
     let quote = dlmm.get_quote(&quote_params).await?;
@@ -161,7 +756,30 @@ async fn main() -> Result<(), Box<dyn Error>> {
     // 3. Get pool statistics
     println!("\n📈 Getting DLMM pair statistics...");
     let pool_stats = dlmm.get_pool_stats("test_pair").await?;
-    println!("✅ Total Liquidity: ${:,.0}", pool_stats);
+    println!("✅ Total Liquidity: ${:.0}", pool_stats.tvl);
+    println!("   Spot Price: {:.4} USDC", pool_stats.spot_price);
+    println!("   Est. 24h Fees: ${:.2}", pool_stats.fees_24h);
+
+    // 4. Add liquidity around the active bin (bin 4607 at the 100.0 fixed rate)
+    println!("\n💧 Adding liquidity with a Curve distribution...");
+    let tokens_provided = vec![
+        TokenAmount {
+            symbol: "SOL".to_string(),
+            amount: 10.0,
+        },
+        TokenAmount {
+            symbol: "USDC".to_string(),
+            amount: 1_000.0,
+        },
+    ];
+    let position = dlmm
+        .add_liquidity(4597, 4617, LiquidityStrategy::Curve, &tokens_provided)
+        .await?;
+    println!(
+        "✅ Liquidity placed across {} bins, leftover: {:?}",
+        position.allocations.len(),
+        position.leftover
+    );
 
     println!("\n✨ Enhanced DLMM Rust example completed successfully!");
 
@@ -177,6 +795,8 @@ mod tests {
         let config = DlmmConfig {
             network: "devnet".to_string(),
             slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
         };
         
         let client = EnhancedSarosDLMM::new(config);
@@ -188,6 +808,8 @@ mod tests {
         let config = DlmmConfig {
             network: "devnet".to_string(),
             slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
         };
         
         let dlmm = EnhancedSarosDLMM::new(config).unwrap();
@@ -197,6 +819,7 @@ mod tests {
             output_token: "USDC".to_string(),
             amount: 1.0,
             wallet_public_key: "test_wallet".to_string(),
+            slippage_override: None,
         };
         
         let quote = dlmm.get_quote(&quote_params).await;
@@ -206,4 +829,324 @@ mod tests {
         assert_eq!(quote.input_amount, 1.0);
         assert!(quote.expected_output > 0.0);
     }
+
+    #[tokio::test]
+    async fn test_quote_base_to_quote_tracks_spot_price() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+
+        let dlmm = EnhancedSarosDLMM::new(config).unwrap();
+
+        let quote_params = SwapParams {
+            input_token: "SOL".to_string(),
+            output_token: "USDC".to_string(),
+            amount: 1.0,
+            wallet_public_key: "test_wallet".to_string(),
+            slippage_override: None,
+        };
+
+        let quote = dlmm.get_quote(&quote_params).await.unwrap();
+
+        // Small trades against deep liquidity should execute close to the 100
+        // USDC/SOL fixed rate, net of the 0.3% fee.
+        assert!((quote.expected_output - 99.7).abs() < 0.1);
+        assert!(quote.price_impact >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_quote_routes_by_configured_pair_not_hardcoded_symbol() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "ETH".to_string(),
+            quote_symbol: "USDT".to_string(),
+        };
+        let dlmm =
+            EnhancedSarosDLMM::with_rate_service(config, Box::new(FixedRate { rate: 100.0 }))
+                .unwrap();
+
+        // Selling 100 USDT (the quote leg) should buy ~1 ETH, not be
+        // misrouted as a base-leg sale that returns ~10_000.
+        let quote_params = SwapParams {
+            input_token: "USDT".to_string(),
+            output_token: "ETH".to_string(),
+            amount: 100.0,
+            wallet_public_key: "test_wallet".to_string(),
+            slippage_override: None,
+        };
+        let quote = dlmm.get_quote(&quote_params).await.unwrap();
+        assert!(
+            quote.expected_output < 2.0,
+            "expected ~1 ETH out, got {}",
+            quote.expected_output
+        );
+
+        // A pair that doesn't match the client's configured base/quote is rejected.
+        let bad_params = SwapParams {
+            input_token: "BTC".to_string(),
+            output_token: "USDT".to_string(),
+            amount: 1.0,
+            wallet_public_key: "test_wallet".to_string(),
+            slippage_override: None,
+        };
+        assert!(dlmm.get_quote(&bad_params).await.is_err());
+    }
+
+    #[test]
+    fn test_invalid_slippage_rejected_at_construction() {
+        let too_low = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.0,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        assert!(EnhancedSarosDLMM::new(too_low).is_err());
+
+        let too_high = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 150.0,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        assert!(EnhancedSarosDLMM::new(too_high).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_slippage_override_validated_per_call() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        let dlmm = EnhancedSarosDLMM::new(config).unwrap();
+
+        let params = SwapParams {
+            input_token: "SOL".to_string(),
+            output_token: "USDC".to_string(),
+            amount: 1.0,
+            wallet_public_key: "test_wallet".to_string(),
+            slippage_override: Some(0.0),
+        };
+        assert!(dlmm.get_quote(&params).await.is_err());
+    }
+
+    /// A `RateService` that returns a different rate on each successive call,
+    /// used to simulate the price moving between a quote and its re-quote.
+    struct SteppedRate {
+        rates: std::sync::Mutex<std::collections::VecDeque<f64>>,
+    }
+
+    #[async_trait]
+    impl RateService for SteppedRate {
+        async fn current_rate(&self, _base: &str, _quote: &str) -> Result<f64> {
+            let mut rates = self.rates.lock().unwrap();
+            Ok(rates.pop_front().unwrap_or(100.0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_swap_aborts_on_slippage_exceeded() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.1,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        let rates = SteppedRate {
+            rates: std::sync::Mutex::new(std::collections::VecDeque::from(vec![100.0, 50.0])),
+        };
+        let dlmm = EnhancedSarosDLMM::with_rate_service(config, Box::new(rates)).unwrap();
+
+        let params = SwapParams {
+            input_token: "SOL".to_string(),
+            output_token: "USDC".to_string(),
+            amount: 1.0,
+            wallet_public_key: "test_wallet".to_string(),
+            slippage_override: None,
+        };
+
+        let err = dlmm.execute_swap(&params).await.unwrap_err();
+        assert!(err.to_string().contains("slippage exceeded"));
+    }
+
+    #[tokio::test]
+    async fn test_add_liquidity_spot_distributes_evenly_and_remove_liquidity_roundtrips() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        let dlmm = EnhancedSarosDLMM::new(config).unwrap();
+
+        let tokens_provided = vec![
+            TokenAmount {
+                symbol: "SOL".to_string(),
+                amount: 10.0,
+            },
+            TokenAmount {
+                symbol: "USDC".to_string(),
+                amount: 1_000.0,
+            },
+        ];
+        let position = dlmm
+            .add_liquidity(-5, 5, LiquidityStrategy::Spot, &tokens_provided)
+            .await
+            .unwrap();
+
+        assert_eq!(position.allocations.len(), 11);
+        assert!(position.allocations.iter().all(|a| a.amount0 >= 0.0 && a.amount1 >= 0.0));
+
+        let (amount0, amount1) = dlmm.remove_liquidity(&position).unwrap();
+        assert!(amount0 > 0.0);
+        assert!(amount1 > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_add_liquidity_single_sided_deposit_places_only_the_provided_token() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        let dlmm = EnhancedSarosDLMM::new(config).unwrap();
+
+        // Only SOL provided; a missing USDC leg must not cap liquidity at 0.
+        let tokens_provided = vec![TokenAmount {
+            symbol: "SOL".to_string(),
+            amount: 10.0,
+        }];
+        let position = dlmm
+            .add_liquidity(-5, 5, LiquidityStrategy::Spot, &tokens_provided)
+            .await
+            .unwrap();
+
+        // The supplied SOL must actually get placed (it used to collapse to
+        // 0 because the missing USDC leg was mistaken for a cap of 0).
+        assert!(
+            position.allocations.iter().any(|a| a.amount0 > 0.0),
+            "single-sided SOL deposit placed no SOL"
+        );
+        assert!(
+            position.allocations.iter().all(|a| a.amount1 == 0.0),
+            "single-sided SOL deposit placed USDC that was never provided"
+        );
+
+        let (amount0, _amount1) = dlmm.remove_liquidity(&position).unwrap();
+        assert!(amount0 > 0.0);
+        assert!(amount0 <= 10.0);
+
+        let leftover_sol = position
+            .leftover
+            .iter()
+            .find(|t| t.symbol == "SOL")
+            .unwrap();
+        assert!(leftover_sol.amount >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_add_liquidity_curve_concentrates_around_live_active_bin() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        let tokens_provided = vec![
+            TokenAmount {
+                symbol: "SOL".to_string(),
+                amount: 10.0,
+            },
+            TokenAmount {
+                symbol: "USDC".to_string(),
+                amount: 1_000.0,
+            },
+        ];
+
+        // A pair priced at 100.0 concentrates around bin ~4607; the same pair
+        // priced at 400.0 concentrates around bin ~5994. A range spanning both
+        // proves `add_liquidity` follows the live `rate_service` rather than a
+        // fixed construction-time price (it used to return bin 4607 either way).
+        let low_price =
+            EnhancedSarosDLMM::with_rate_service(config.clone(), Box::new(FixedRate { rate: 100.0 }))
+                .unwrap();
+        let high_price =
+            EnhancedSarosDLMM::with_rate_service(config, Box::new(FixedRate { rate: 400.0 }))
+                .unwrap();
+
+        let low = low_price
+            .add_liquidity(4550, 6050, LiquidityStrategy::Curve, &tokens_provided)
+            .await
+            .unwrap();
+        let high = high_price
+            .add_liquidity(4550, 6050, LiquidityStrategy::Curve, &tokens_provided)
+            .await
+            .unwrap();
+
+        let heaviest_bin = |position: &LiquidityPosition| {
+            position
+                .allocations
+                .iter()
+                .max_by(|a, b| {
+                    (a.amount0 + a.amount1)
+                        .partial_cmp(&(b.amount0 + b.amount1))
+                        .unwrap()
+                })
+                .unwrap()
+                .bin_id
+        };
+
+        assert_ne!(heaviest_bin(&low), heaviest_bin(&high));
+    }
+
+    #[tokio::test]
+    async fn test_with_reserves_falls_back_to_constant_product_quoting() {
+        let config = DlmmConfig {
+            network: "devnet".to_string(),
+            slippage: 0.5,
+            base_symbol: "SOL".to_string(),
+            quote_symbol: "USDC".to_string(),
+        };
+        let dlmm = EnhancedSarosDLMM::new(config)
+            .unwrap()
+            .with_reserves(PoolReserves {
+                reserve_in: 1_000.0,
+                reserve_out: 100_000.0,
+                fee_rate: 0.003,
+            });
+
+        let params = SwapParams {
+            input_token: "SOL".to_string(),
+            output_token: "USDC".to_string(),
+            amount: 1.0,
+            wallet_public_key: "test_wallet".to_string(),
+            slippage_override: None,
+        };
+        let quote = dlmm.get_quote(&params).await.unwrap();
+
+        // Constant-product math on a 1_000/100_000 pool selling 1.0: not the
+        // ~99.7 a bin-priced pool would return at the 100.0 fixed rate.
+        let amount_in_after_fee = 1.0 * (1.0 - 0.003);
+        let expected = (100_000.0 * amount_in_after_fee) / (1_000.0 + amount_in_after_fee);
+        assert!((quote.expected_output - expected).abs() < 1e-6);
+
+        let stats = dlmm.get_pool_stats("test_pair").await.unwrap();
+        assert!(stats.tvl > 0.0);
+
+        // No concentrated-liquidity state means bin-based deposits are rejected.
+        let tokens_provided = vec![TokenAmount {
+            symbol: "SOL".to_string(),
+            amount: 10.0,
+        }];
+        assert!(dlmm
+            .add_liquidity(-5, 5, LiquidityStrategy::Spot, &tokens_provided)
+            .await
+            .is_err());
+    }
 }